@@ -1,9 +1,9 @@
+use std::borrow::Cow;
 use std::mem::size_of;
 use std::path::PathBuf;
 
-use bytemuck::{Pod, Zeroable};
-use heed::BytesDecode;
-use heed_types::{ByteSlice, CowType, SerdeBincode};
+use heed::{BoxedError, BytesDecode, BytesEncode};
+use heed_types::SerdeBincode;
 use libsqlx::FrameNo;
 use serde::{Deserialize, Serialize};
 use tokio::task::block_in_place;
@@ -11,8 +11,46 @@ use uuid::Uuid;
 
 use crate::meta::DatabaseId;
 
-#[derive(Clone, Copy, Zeroable, Pod, Debug)]
-#[repr(transparent)]
+/// A versioned, machine-portable wire format for types stored in the snapshot store.
+///
+/// Unlike deriving `bytemuck::Pod` or relying on `bincode`'s default representation, a
+/// `StorageSerde` encoding is explicitly specified: it does not depend on struct layout, host
+/// endianness, or padding, so it can be read back by a different build or a different machine
+/// than the one that wrote it.
+///
+/// Named `to_bytes`/`from_bytes` rather than `serialize`/`deserialize` because `SnapshotMeta`
+/// also derives `serde::Serialize`/`Deserialize` for its legacy bincode fallback below; sharing
+/// the method names with that derive would make calls through either trait ambiguous.
+trait StorageSerde: Sized {
+    /// Append `self`'s encoding to `buf`.
+    fn to_bytes(&self, buf: &mut Vec<u8>);
+    /// Decode a `Self` from the front of `buf`, advancing `buf` past the bytes it consumed.
+    fn from_bytes(buf: &mut &[u8]) -> Option<Self>;
+}
+
+/// A `heed::BytesEncode`/`BytesDecode` adapter bridging any [`StorageSerde`] type into heed.
+struct Storage<T>(std::marker::PhantomData<T>);
+
+impl<'a, T: StorageSerde + 'a> BytesEncode<'a> for Storage<T> {
+    type EItem = T;
+
+    fn bytes_encode(item: &'a T) -> Result<Cow<'a, [u8]>, BoxedError> {
+        let mut buf = Vec::new();
+        item.to_bytes(&mut buf);
+        Ok(Cow::Owned(buf))
+    }
+}
+
+impl<'a, T: StorageSerde + 'a> BytesDecode<'a> for Storage<T> {
+    type DItem = T;
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<T, BoxedError> {
+        let mut cursor = bytes;
+        T::from_bytes(&mut cursor).ok_or_else(|| "invalid snapshot store encoding".into())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
 struct BEU64([u8; size_of::<u64>()]);
 
 impl From<u64> for BEU64 {
@@ -27,38 +65,146 @@ impl From<BEU64> for u64 {
     }
 }
 
-#[derive(Clone, Copy, Zeroable, Pod, Debug)]
-#[repr(C)]
+#[derive(Clone, Copy, Debug)]
 struct SnapshotKey {
     database_id: DatabaseId,
     start_frame_no: BEU64,
     end_frame_no: BEU64,
 }
 
+impl StorageSerde for SnapshotKey {
+    /// Keys are encoded field-by-field, in declaration order. `start_frame_no` and
+    /// `end_frame_no` go through [`BEU64`], which canonicalizes the scalar frame numbers to a
+    /// fixed big-endian representation. `database_id` is written out via its own raw `Pod`
+    /// bytes, untouched: it's a content-addressed identifier rather than a scalar integer, so
+    /// unlike `BEU64` there is no meaningful "byte order" to canonicalize — reversing its bytes
+    /// per-host would instead produce a *different* encoding on little- vs big-endian hosts for
+    /// the same id, defeating the portability this format exists for. `database_id` must stay
+    /// the first field so range scans like [`SnapshotStore::list`] keep working.
+    fn to_bytes(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(bytemuck::bytes_of(&self.database_id));
+        buf.extend_from_slice(&self.start_frame_no.0);
+        buf.extend_from_slice(&self.end_frame_no.0);
+    }
+
+    fn from_bytes(buf: &mut &[u8]) -> Option<Self> {
+        let database_id_size = size_of::<DatabaseId>();
+        if buf.len() < database_id_size + size_of::<u64>() * 2 {
+            return None;
+        }
+
+        let (database_id_bytes, rest) = buf.split_at(database_id_size);
+        let database_id = *bytemuck::try_from_bytes(database_id_bytes).ok()?;
+        let (start_frame_no_bytes, rest) = rest.split_at(size_of::<u64>());
+        let (end_frame_no_bytes, rest) = rest.split_at(size_of::<u64>());
+
+        *buf = rest;
+        Some(SnapshotKey {
+            database_id,
+            start_frame_no: BEU64(start_frame_no_bytes.try_into().unwrap()),
+            end_frame_no: BEU64(end_frame_no_bytes.try_into().unwrap()),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct SnapshotMeta {
     pub snapshot_id: Uuid,
 }
 
+impl SnapshotMeta {
+    /// Bump this when `SnapshotMeta` gains or changes fields, and branch on it in
+    /// `from_bytes` to keep reading data written under older versions.
+    const VERSION: u8 = 1;
+
+    /// Total size of a version-tagged record: the one-byte tag plus 16 raw `snapshot_id` bytes.
+    /// `bincode` frames a `Uuid` with an 8-byte length prefix ahead of its bytes, so a legacy
+    /// record is never exactly this long; that lets `from_bytes` tell the two formats apart by
+    /// length alone instead of guessing from the leading byte, which legacy data could just as
+    /// well produce by chance.
+    const V1_LEN: usize = 1 + 16;
+}
+
+impl StorageSerde for SnapshotMeta {
+    /// Version 1: a one-byte tag followed by the 16 raw bytes of `snapshot_id`. Records that
+    /// aren't exactly `Self::V1_LEN` bytes long predate versioning entirely, so they're decoded
+    /// as the original `bincode`-encoded value instead, letting stores written before this
+    /// format existed keep reading correctly.
+    fn to_bytes(&self, buf: &mut Vec<u8>) {
+        buf.push(Self::VERSION);
+        buf.extend_from_slice(self.snapshot_id.as_bytes());
+    }
+
+    fn from_bytes(buf: &mut &[u8]) -> Option<Self> {
+        if buf.len() == Self::V1_LEN && buf[0] == Self::VERSION {
+            let id_bytes = &buf[1..Self::V1_LEN];
+            let snapshot_id = Uuid::from_slice(id_bytes).ok()?;
+            *buf = &buf[Self::V1_LEN..];
+            return Some(SnapshotMeta { snapshot_id });
+        }
+
+        let meta = SerdeBincode::<SnapshotMeta>::bytes_decode(buf).ok()?;
+        *buf = &[];
+        Some(meta)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotStoreError {
+    #[error("no snapshot covers frame {missing_from}, and none follow it in the chain")]
+    SnapshotGap { missing_from: FrameNo },
+}
+
 pub struct SnapshotStore {
     env: heed::Env,
-    database: heed::Database<CowType<SnapshotKey>, SerdeBincode<SnapshotMeta>>,
+    database: heed::Database<Storage<SnapshotKey>, Storage<SnapshotMeta>>,
     db_path: PathBuf,
 }
 
+/// Storage accounting for a single database's snapshots, as reported by [`SnapshotStore::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SnapshotStoreStats {
+    /// How many snapshots are registered for the database.
+    pub snapshot_count: u64,
+    /// The lowest `start_frame_no` covered by any of its snapshots, if it has any.
+    pub min_frame_no: Option<FrameNo>,
+    /// The highest `end_frame_no` covered by any of its snapshots, if it has any.
+    pub max_frame_no: Option<FrameNo>,
+    /// The sum of `end_frame_no - start_frame_no + 1` across all of its snapshots. This can
+    /// exceed `max_frame_no - min_frame_no + 1` when snapshots overlap.
+    pub total_frames_covered: u64,
+}
+
 impl SnapshotStore {
     const SNAPSHOT_STORE_NAME: &str = "snapshot-store-db";
 
+    /// Open (or create) the snapshot store backed by `env`. If the environment's map is already
+    /// full, its map size is doubled in place (an `mdb_env_set_mapsize`-style resize, not a
+    /// reopen) and creation is retried, so a transient `MDB_MAP_FULL` doesn't fail startup and
+    /// every other option the caller originally opened `env` with (e.g. `max_dbs`) is preserved.
     pub fn new(db_path: PathBuf, env: heed::Env) -> color_eyre::Result<Self> {
-        let mut txn = env.write_txn().unwrap();
-        let database = env.create_database(&mut txn, Some(Self::SNAPSHOT_STORE_NAME))?;
-        txn.commit()?;
-
-        Ok(Self {
-            database,
-            db_path,
-            env,
-        })
+        loop {
+            let mut txn = env.write_txn()?;
+            let created = env
+                .create_database(&mut txn, Some(Self::SNAPSHOT_STORE_NAME))
+                .and_then(|database| txn.commit().map(|()| database));
+            match created {
+                Ok(database) => {
+                    return Ok(Self {
+                        database,
+                        db_path,
+                        env,
+                    });
+                }
+                Err(heed::Error::Mdb(heed::MdbError::MapFull)) => {
+                    let new_map_size = env.info().map_size * 2;
+                    // Safety: no transactions are open on `env` at this point, which is heed's
+                    // only requirement for resizing live.
+                    unsafe { env.resize(new_map_size)? };
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
     }
 
     pub fn register(
@@ -68,7 +214,7 @@ impl SnapshotStore {
         start_frame_no: FrameNo,
         end_frame_no: FrameNo,
         snapshot_id: Uuid,
-    ) {
+    ) -> color_eyre::Result<()> {
         let key = SnapshotKey {
             database_id,
             start_frame_no: start_frame_no.into(),
@@ -77,12 +223,47 @@ impl SnapshotStore {
 
         let data = SnapshotMeta { snapshot_id };
 
-        block_in_place(|| self.database.put(txn, &key, &data).unwrap());
+        block_in_place(|| self.database.put(txn, &key, &data))?;
+        Ok(())
+    }
+
+    /// The name `backup_to` gives the single flat data file it writes into `dest`, and the name
+    /// [`Self::restore_from`] expects to find there. heed's `copy_to_file` writes one file with no
+    /// accompanying lockfile, so the pair only work together by opening that file in
+    /// `NO_SUB_DIR` mode rather than as a directory-backed environment.
+    const BACKUP_FILE_NAME: &str = "data.mdb";
+
+    /// Take a consistent, compacted, point-in-time copy of the snapshot metadata environment into
+    /// `dest` (a directory, created if missing), without interrupting concurrent reads or writes.
+    /// Pair with [`Self::restore_from`] to open the copy back up.
+    pub fn backup_to(&self, dest: PathBuf) -> color_eyre::Result<()> {
+        std::fs::create_dir_all(&dest)?;
+        self.env.copy_to_file(
+            dest.join(Self::BACKUP_FILE_NAME),
+            heed::CompactionOption::Enabled,
+        )?;
+        Ok(())
+    }
+
+    /// Open a backup produced by [`Self::backup_to`] read-only, for inspection or restore.
+    pub fn restore_from(backup_dir: PathBuf) -> color_eyre::Result<heed::Env> {
+        let env = unsafe {
+            heed::EnvOpenOptions::new()
+                .max_dbs(1)
+                .flags(heed::EnvFlags::READ_ONLY | heed::EnvFlags::NO_SUB_DIR)
+                .open(backup_dir.join(Self::BACKUP_FILE_NAME))?
+        };
+
+        Ok(env)
     }
 
     /// Locate a snapshot for `database_id` that contains `frame_no`
-    pub fn locate(&self, database_id: DatabaseId, frame_no: FrameNo) -> Option<SnapshotMeta> {
-        let txn = self.env.read_txn().unwrap();
+    pub fn locate(
+        &self,
+        database_id: DatabaseId,
+        frame_no: FrameNo,
+    ) -> color_eyre::Result<Option<SnapshotMeta>> {
+        let txn = self.env.read_txn()?;
         // Snapshot keys being lexicographically ordered, looking for the first key less than of
         // equal to (db_id, frame_no, FrameNo::MAX) will always return the entry we're looking for
         // if it exists.
@@ -92,25 +273,477 @@ impl SnapshotStore {
             end_frame_no: u64::MAX.into(),
         };
 
-        match self
-            .database
-            .get_lower_than_or_equal_to(&txn, &key)
-            .transpose()?
-        {
-            Ok((key, v)) => {
-                if key.database_id != database_id {
-                    return None;
-                } else if frame_no >= key.start_frame_no.into()
-                    && frame_no <= key.end_frame_no.into()
-                {
-                    return Some(v);
-                } else {
-                    None
+        let Some((key, v)) = self.database.get_lower_than_or_equal_to(&txn, &key)? else {
+            return Ok(None);
+        };
+
+        if key.database_id != database_id {
+            return Ok(None);
+        }
+
+        if frame_no >= key.start_frame_no.into() && frame_no <= key.end_frame_no.into() {
+            Ok(Some(v))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Locate the chain of snapshots covering `[from, to]` for `database_id`, in order.
+    ///
+    /// This is the classic "minimum intervals to cover a range" greedy: [`Self::list`] already
+    /// hands back every snapshot for `database_id` sorted by `start_frame_no`, so a single
+    /// forward sweep with a cursor into that list suffices — at each step we advance past every
+    /// entry whose `start_frame_no` is at or before the cursor, track the one with the largest
+    /// `end_frame_no` among them (so the chain stays as short as possible), push it, and move
+    /// the cursor just past it. Because the sweep index only ever moves forward, the whole walk
+    /// is a single O(snapshot_count) pass rather than re-scanning from the start at every step.
+    /// If no snapshot covers the cursor, returns [`SnapshotStoreError::SnapshotGap`].
+    pub fn locate_chain(
+        &self,
+        database_id: DatabaseId,
+        from: FrameNo,
+        to: FrameNo,
+    ) -> color_eyre::Result<Vec<SnapshotMeta>> {
+        let entries: Vec<_> = self.list(database_id)?.collect();
+
+        let mut chain = Vec::new();
+        let mut cursor = from;
+        let mut i = 0;
+
+        while cursor <= to {
+            let mut best: Option<(FrameNo, &SnapshotMeta)> = None;
+            while i < entries.len() && entries[i].0 <= cursor {
+                let (_, end_frame_no, meta) = &entries[i];
+                let is_better = match best {
+                    Some((best_end, _)) => *end_frame_no > best_end,
+                    None => true,
+                };
+                if is_better {
+                    best = Some((*end_frame_no, meta));
+                }
+                i += 1;
+            }
+
+            match best {
+                Some((end_frame_no, meta)) if end_frame_no >= cursor => {
+                    chain.push(*meta);
+                    cursor = end_frame_no + 1;
+                }
+                _ => {
+                    return Err(SnapshotStoreError::SnapshotGap {
+                        missing_from: cursor,
+                    }
+                    .into())
                 }
             }
-            Err(_) => todo!(),
         }
+
+        Ok(chain)
+    }
+
+    /// Enumerate every snapshot registered for `database_id`, ordered by `start_frame_no`.
+    ///
+    /// This is a prefix range scan: keys are ordered first by `database_id`, so we start from
+    /// `(database_id, 0, 0)` and walk forward until we step into the next database's keys.
+    pub fn list(
+        &self,
+        database_id: DatabaseId,
+    ) -> color_eyre::Result<impl Iterator<Item = (FrameNo, FrameNo, SnapshotMeta)>> {
+        let txn = self.env.read_txn()?;
+        let start = SnapshotKey {
+            database_id,
+            start_frame_no: 0.into(),
+            end_frame_no: 0.into(),
+        };
+
+        let mut entries = Vec::new();
+        for entry in self.database.range(&txn, &(start..))? {
+            let (key, meta) = entry?;
+            if key.database_id != database_id {
+                break;
+            }
+            entries.push((key.start_frame_no.into(), key.end_frame_no.into(), meta));
+        }
+
+        Ok(entries.into_iter())
+    }
+
+    /// Delete every snapshot registered for `database_id` whose `end_frame_no` is strictly less
+    /// than `frame_no`, returning the `snapshot_id`s of the entries that were removed so the
+    /// caller can reclaim the backing snapshot files.
+    pub fn delete_up_to(
+        &self,
+        txn: &mut heed::RwTxn,
+        database_id: DatabaseId,
+        frame_no: FrameNo,
+    ) -> color_eyre::Result<Vec<Uuid>> {
+        let start = SnapshotKey {
+            database_id,
+            start_frame_no: 0.into(),
+            end_frame_no: 0.into(),
+        };
+
+        let mut to_delete = Vec::new();
+        for entry in self.database.range(txn, &(start..))? {
+            let (key, meta) = entry?;
+            if key.database_id != database_id {
+                break;
+            }
+            if u64::from(key.end_frame_no) < frame_no {
+                to_delete.push((key, meta.snapshot_id));
+            }
+        }
+
+        let mut freed = Vec::with_capacity(to_delete.len());
+        for (key, snapshot_id) in to_delete {
+            block_in_place(|| self.database.delete(txn, &key))?;
+            freed.push(snapshot_id);
+        }
+
+        Ok(freed)
+    }
+
+    /// Report the snapshot count and frame coverage for `database_id`. Useful for metrics and
+    /// for GC heuristics deciding when pruning or compaction is worthwhile.
+    pub fn stats(&self, database_id: DatabaseId) -> color_eyre::Result<SnapshotStoreStats> {
+        let mut stats = SnapshotStoreStats::default();
+
+        for (start_frame_no, end_frame_no, _) in self.list(database_id)? {
+            stats.snapshot_count += 1;
+            stats.min_frame_no = Some(match stats.min_frame_no {
+                Some(min) => min.min(start_frame_no),
+                None => start_frame_no,
+            });
+            stats.max_frame_no = Some(match stats.max_frame_no {
+                Some(max) => max.max(end_frame_no),
+                None => end_frame_no,
+            });
+            stats.total_frames_covered += end_frame_no - start_frame_no + 1;
+        }
+
+        Ok(stats)
+    }
+
+    /// The number of bytes the on-disk environment at `db_path` currently occupies.
+    pub fn env_size(&self) -> u64 {
+        fn dir_size(path: &std::path::Path) -> u64 {
+            let Ok(entries) = std::fs::read_dir(path) else {
+                return 0;
+            };
+
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| match entry.metadata() {
+                    Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+                    Ok(meta) => meta.len(),
+                    Err(_) => 0,
+                })
+                .sum()
+        }
+
+        dir_size(&self.db_path)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    /// Build a `DatabaseId` with a distinct bit pattern from `tag`, without assuming anything
+    /// about `DatabaseId`'s own API beyond the `Pod`/`Zeroable` impls `SnapshotKey::to_bytes`
+    /// already requires of it.
+    fn database_id(tag: u8) -> DatabaseId {
+        let mut bytes = vec![0u8; size_of::<DatabaseId>()];
+        if let Some(last) = bytes.last_mut() {
+            *last = tag;
+        }
+        *bytemuck::from_bytes(&bytes)
+    }
+
+    fn test_store() -> (tempfile::TempDir, SnapshotStore) {
+        let dir = tempdir().unwrap();
+        let env = unsafe {
+            heed::EnvOpenOptions::new()
+                .map_size(10 * 1024 * 1024)
+                .max_dbs(3)
+                .open(dir.path())
+                .unwrap()
+        };
+        let store = SnapshotStore::new(dir.path().to_path_buf(), env).unwrap();
+        (dir, store)
+    }
+
+    #[test]
+    fn new_recovers_from_map_full_by_resizing_in_place() {
+        use heed_types::{Bytes, Str};
+
+        let dir = tempdir().unwrap();
+        let tiny_map_size = 100 * 1024;
+        let env = unsafe {
+            heed::EnvOpenOptions::new()
+                .map_size(tiny_map_size)
+                .max_dbs(3)
+                .open(dir.path())
+                .unwrap()
+        };
+
+        // Fill the map with an unrelated database before `SnapshotStore` ever creates its own,
+        // so `SnapshotStore::new`'s first `create_database` call is the one that hits
+        // `MapFull`, exactly like a process restarting against an already-full environment.
+        let filler: heed::Database<Str, Bytes> = {
+            let mut txn = env.write_txn().unwrap();
+            let filler = env.create_database(&mut txn, Some("filler")).unwrap();
+            txn.commit().unwrap();
+            filler
+        };
+
+        let value = vec![0u8; 2000];
+        let mut next_key = 0u64;
+        'fill: loop {
+            let mut txn = env.write_txn().unwrap();
+            for _ in 0..4 {
+                let key = format!("key-{next_key}");
+                next_key += 1;
+                if let Err(heed::Error::Mdb(heed::MdbError::MapFull)) =
+                    filler.put(&mut txn, &key, value.as_slice())
+                {
+                    break 'fill;
+                }
+            }
+            match txn.commit() {
+                Ok(()) => continue,
+                Err(heed::Error::Mdb(heed::MdbError::MapFull)) => break,
+                Err(e) => panic!("unexpected error filling the map: {e}"),
+            }
+        }
+
+        // The environment's map is now full: `new` must resize it in place (preserving the
+        // `max_dbs(3)` the caller configured) rather than reopening with heed's defaults
+        // (`max_dbs` 0), or `create_database` below fails all over again with `DbsFull`.
+        let store = SnapshotStore::new(dir.path().to_path_buf(), env).unwrap();
+
+        let db = database_id(1);
+        let snapshot_id = Uuid::from_u128(1);
+        let mut txn = store.env.write_txn().unwrap();
+        store.register(&mut txn, db, 0, 1, snapshot_id).unwrap();
+        txn.commit().unwrap();
+
+        assert_eq!(store.locate(db, 0).unwrap().unwrap().snapshot_id, snapshot_id);
+    }
+
+    #[test]
+    fn list_orders_by_start_frame_no_and_stops_at_the_database_boundary() {
+        let (_dir, store) = test_store();
+        let db_a = database_id(1);
+        let db_b = database_id(2);
+
+        let mut txn = store.env.write_txn().unwrap();
+        // Registered out of order, and interleaved with another database, to make sure `list`
+        // sorts by `start_frame_no` and doesn't leak entries belonging to `db_b`.
+        store
+            .register(&mut txn, db_a, 100, 199, Uuid::from_u128(2))
+            .unwrap();
+        store.register(&mut txn, db_b, 0, 50, Uuid::from_u128(3)).unwrap();
+        store.register(&mut txn, db_a, 0, 99, Uuid::from_u128(1)).unwrap();
+        txn.commit().unwrap();
+
+        let entries: Vec<_> = store
+            .list(db_a)
+            .unwrap()
+            .map(|(start, end, meta)| (start, end, meta.snapshot_id))
+            .collect();
+
+        assert_eq!(
+            entries,
+            vec![
+                (0, 99, Uuid::from_u128(1)),
+                (100, 199, Uuid::from_u128(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_up_to_removes_only_snapshots_fully_below_the_cutoff() {
+        let (_dir, store) = test_store();
+        let db = database_id(1);
+        let dropped = Uuid::from_u128(1);
+        let kept = Uuid::from_u128(2);
+
+        let mut txn = store.env.write_txn().unwrap();
+        store.register(&mut txn, db, 0, 99, dropped).unwrap();
+        store.register(&mut txn, db, 100, 199, kept).unwrap();
+        let freed = store.delete_up_to(&mut txn, db, 100).unwrap();
+        txn.commit().unwrap();
+
+        assert_eq!(freed, vec![dropped]);
+
+        let remaining: Vec<_> = store
+            .list(db)
+            .unwrap()
+            .map(|(_, _, meta)| meta.snapshot_id)
+            .collect();
+        assert_eq!(remaining, vec![kept]);
+    }
+
+    #[test]
+    fn locate_chain_prefers_largest_end_frame_no_among_overlapping_snapshots() {
+        let (_dir, store) = test_store();
+        let db = database_id(1);
+        let short = Uuid::from_u128(1);
+        let long = Uuid::from_u128(2);
+
+        let mut txn = store.env.write_txn().unwrap();
+        // `short` starts later than `long` but covers far less: a point lookup keyed on
+        // `start_frame_no` would wrongly prefer it over `long`.
+        store.register(&mut txn, db, 55, 90, short).unwrap();
+        store.register(&mut txn, db, 50, 200, long).unwrap();
+        txn.commit().unwrap();
+
+        let chain = store.locate_chain(db, 60, 200).unwrap();
+        assert_eq!(chain.len(), 1);
+        assert_eq!(chain[0].snapshot_id, long);
+    }
+
+    #[test]
+    fn locate_chain_reports_a_gap() {
+        let (_dir, store) = test_store();
+        let db = database_id(1);
+
+        let mut txn = store.env.write_txn().unwrap();
+        store.register(&mut txn, db, 0, 99, Uuid::from_u128(1)).unwrap();
+        store
+            .register(&mut txn, db, 150, 199, Uuid::from_u128(2))
+            .unwrap();
+        txn.commit().unwrap();
+
+        let err = store.locate_chain(db, 0, 199).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<SnapshotStoreError>(),
+            Some(SnapshotStoreError::SnapshotGap { missing_from: 100 })
+        ));
+    }
+
+    #[test]
+    fn backup_to_produces_a_readable_point_in_time_copy() {
+        let (_dir, store) = test_store();
+        let db = database_id(9);
+        let snapshot_id = Uuid::from_u128(1);
+
+        let mut txn = store.env.write_txn().unwrap();
+        store.register(&mut txn, db, 0, 10, snapshot_id).unwrap();
+        txn.commit().unwrap();
+
+        let backup_dir = tempdir().unwrap();
+        store.backup_to(backup_dir.path().to_path_buf()).unwrap();
+
+        let restored_env = SnapshotStore::restore_from(backup_dir.path().to_path_buf()).unwrap();
+        let restore_txn = restored_env.read_txn().unwrap();
+        let restored_db = restored_env
+            .open_database::<Storage<SnapshotKey>, Storage<SnapshotMeta>>(
+                &restore_txn,
+                Some(SnapshotStore::SNAPSHOT_STORE_NAME),
+            )
+            .unwrap()
+            .expect("backup should contain the snapshot-store database");
+
+        let key = SnapshotKey {
+            database_id: db,
+            start_frame_no: 0.into(),
+            end_frame_no: u64::MAX.into(),
+        };
+        let (_, meta) = restored_db
+            .get_lower_than_or_equal_to(&restore_txn, &key)
+            .unwrap()
+            .unwrap();
+        assert_eq!(meta.snapshot_id, snapshot_id);
+    }
+
+    #[test]
+    fn snapshot_key_round_trips_through_storage_serde() {
+        let key = SnapshotKey {
+            database_id: database_id(7),
+            start_frame_no: 42.into(),
+            end_frame_no: 1000.into(),
+        };
+
+        let mut buf = Vec::new();
+        key.to_bytes(&mut buf);
+
+        let mut cursor = buf.as_slice();
+        let decoded = SnapshotKey::from_bytes(&mut cursor).unwrap();
+
+        assert!(cursor.is_empty());
+        assert_eq!(decoded.database_id, key.database_id);
+        assert_eq!(u64::from(decoded.start_frame_no), 42);
+        assert_eq!(u64::from(decoded.end_frame_no), 1000);
+    }
+
+    #[test]
+    fn snapshot_meta_round_trips_through_storage_serde() {
+        let meta = SnapshotMeta {
+            snapshot_id: Uuid::from_u128(123),
+        };
+
+        let mut buf = Vec::new();
+        meta.to_bytes(&mut buf);
+        assert_eq!(buf.len(), SnapshotMeta::V1_LEN);
+
+        let mut cursor = buf.as_slice();
+        let decoded = SnapshotMeta::from_bytes(&mut cursor).unwrap();
+
+        assert!(cursor.is_empty());
+        assert_eq!(decoded.snapshot_id, meta.snapshot_id);
+    }
+
+    #[test]
+    fn stats_reports_snapshot_count_and_frame_span() {
+        let (_dir, store) = test_store();
+        let db = database_id(3);
+
+        let mut txn = store.env.write_txn().unwrap();
+        store
+            .register(&mut txn, db, 0, 99, Uuid::from_u128(1))
+            .unwrap();
+        store
+            .register(&mut txn, db, 100, 149, Uuid::from_u128(2))
+            .unwrap();
+        txn.commit().unwrap();
+
+        let stats = store.stats(db).unwrap();
+        assert_eq!(stats.snapshot_count, 2);
+        assert_eq!(stats.min_frame_no, Some(0));
+        assert_eq!(stats.max_frame_no, Some(149));
+        assert_eq!(stats.total_frames_covered, 150);
+    }
+
+    #[test]
+    fn stats_is_default_for_a_database_with_no_snapshots() {
+        let (_dir, store) = test_store();
+        assert_eq!(
+            store.stats(database_id(1)).unwrap(),
+            SnapshotStoreStats::default()
+        );
+    }
+
+    #[test]
+    fn snapshot_meta_falls_back_to_legacy_bincode_encoding() {
+        let meta = SnapshotMeta {
+            snapshot_id: Uuid::from_u128(456),
+        };
+
+        let legacy_bytes = SerdeBincode::<SnapshotMeta>::bytes_encode(&meta)
+            .unwrap()
+            .into_owned();
+        assert_ne!(
+            legacy_bytes.len(),
+            SnapshotMeta::V1_LEN,
+            "legacy encoding must stay distinguishable by length from the versioned one"
+        );
+
+        let mut cursor = legacy_bytes.as_slice();
+        let decoded = SnapshotMeta::from_bytes(&mut cursor).unwrap();
+        assert_eq!(decoded.snapshot_id, meta.snapshot_id);
     }
 }